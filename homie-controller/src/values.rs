@@ -1,4 +1,8 @@
 use crate::types::Datatype;
+#[cfg(feature = "serde")]
+use serde::de::Error as _;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::{self, Debug, Display, Formatter};
 use std::num::ParseIntError;
 use std::ops::RangeInclusive;
@@ -170,6 +174,22 @@ impl Display for ColorFormat {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for ColorFormat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ColorFormat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| D::Error::invalid_value(serde::de::Unexpected::Str(&s), &"\"rgb\" or \"hsv\""))
+    }
+}
+
 pub trait Color: Value {
     fn format() -> ColorFormat;
 }
@@ -198,13 +218,139 @@ impl<T: Color> Value for T {
 
 /// An error while attempting to parse a `Color` from a string.
 #[derive(Clone, Debug, Error, Eq, PartialEq)]
-#[error("Failed to parse color.")]
-pub struct ParseColorError();
+pub enum ParseColorError {
+    #[error("Failed to parse color.")]
+    InvalidFormat,
+    #[error("'{0}' is not a valid hex color length; expected 3 or 6 hex digits.")]
+    InvalidHexLength(String),
+    #[error("'{0}' is not a recognised color name.")]
+    UnknownName(String),
+}
 
 impl From<ParseIntError> for ParseColorError {
     fn from(_: ParseIntError) -> Self {
-        ParseColorError()
+        ParseColorError::InvalidFormat
+    }
+}
+
+/// Look up an X11 colour name, returning its (r, g, b) triple. The name must already be lowercase.
+fn x11_color_by_name(name: &str) -> Option<(u8, u8, u8)> {
+    X11_COLORS
+        .iter()
+        .find(|(candidate, _)| *candidate == name)
+        .map(|(_, rgb)| *rgb)
+}
+
+/// A table of standard X11 colour names to their (r, g, b) values, as used e.g. by CSS.
+const X11_COLORS: &[(&str, (u8, u8, u8))] = &[
+    ("black", (0, 0, 0)),
+    ("silver", (192, 192, 192)),
+    ("gray", (128, 128, 128)),
+    ("grey", (128, 128, 128)),
+    ("white", (255, 255, 255)),
+    ("maroon", (128, 0, 0)),
+    ("red", (255, 0, 0)),
+    ("purple", (128, 0, 128)),
+    ("fuchsia", (255, 0, 255)),
+    ("magenta", (255, 0, 255)),
+    ("green", (0, 128, 0)),
+    ("lime", (0, 255, 0)),
+    ("olive", (128, 128, 0)),
+    ("yellow", (255, 255, 0)),
+    ("navy", (0, 0, 128)),
+    ("blue", (0, 0, 255)),
+    ("teal", (0, 128, 128)),
+    ("aqua", (0, 255, 255)),
+    ("cyan", (0, 255, 255)),
+    ("orange", (255, 165, 0)),
+    ("pink", (255, 192, 203)),
+    ("gold", (255, 215, 0)),
+    ("brown", (165, 42, 42)),
+    ("chocolate", (210, 105, 30)),
+    ("coral", (255, 127, 80)),
+    ("salmon", (250, 128, 114)),
+    ("khaki", (240, 230, 140)),
+    ("violet", (238, 130, 238)),
+    ("indigo", (75, 0, 130)),
+    ("ivory", (255, 255, 240)),
+    ("lavender", (230, 230, 250)),
+    ("plum", (221, 160, 221)),
+    ("orchid", (218, 112, 214)),
+    ("tan", (210, 180, 140)),
+    ("turquoise", (64, 224, 208)),
+    ("skyblue", (135, 206, 235)),
+    ("steelblue", (70, 130, 180)),
+    ("royalblue", (65, 105, 225)),
+    ("cornflowerblue", (100, 149, 237)),
+    ("chartreuse", (127, 255, 0)),
+    ("crimson", (220, 20, 60)),
+    ("darkgreen", (0, 100, 0)),
+    ("darkblue", (0, 0, 139)),
+    ("darkred", (139, 0, 0)),
+    ("darkorange", (255, 140, 0)),
+    ("darkviolet", (148, 0, 211)),
+    ("deeppink", (255, 20, 147)),
+    ("firebrick", (178, 34, 34)),
+    ("forestgreen", (34, 139, 34)),
+    ("goldenrod", (218, 165, 32)),
+    ("hotpink", (255, 105, 180)),
+    ("indianred", (205, 92, 92)),
+    ("lightblue", (173, 216, 230)),
+    ("lightgreen", (144, 238, 144)),
+    ("lightgray", (211, 211, 211)),
+    ("lightgrey", (211, 211, 211)),
+    ("lightpink", (255, 182, 193)),
+    ("lightyellow", (255, 255, 224)),
+    ("limegreen", (50, 205, 50)),
+    ("mediumblue", (0, 0, 205)),
+    ("midnightblue", (25, 25, 112)),
+    ("olivedrab", (107, 142, 35)),
+    ("palegreen", (152, 251, 152)),
+    ("peru", (205, 133, 63)),
+    ("rosybrown", (188, 143, 143)),
+    ("saddlebrown", (139, 69, 19)),
+    ("seagreen", (46, 139, 87)),
+    ("sienna", (160, 82, 45)),
+    ("slateblue", (106, 90, 205)),
+    ("slategray", (112, 128, 144)),
+    ("slategrey", (112, 128, 144)),
+    ("springgreen", (0, 255, 127)),
+    ("tomato", (255, 99, 71)),
+    ("yellowgreen", (154, 205, 50)),
+];
+
+/// Parse a single `#RRGGBB` or `#RGB` hex channel pair into a `u8`.
+fn parse_hex_pair(pair: &str) -> Result<u8, ParseColorError> {
+    Ok(u8::from_str_radix(pair, 16)?)
+}
+
+/// If `s` is a CSS functional notation call to `name` (e.g. `rgb(255, 0, 0)`), split and return its
+/// comma-separated arguments as trimmed substrings. Returns `None` if `s` isn't a call to `name` at
+/// all, to let the caller try other notations. Callers are responsible for interpreting a trailing
+/// `%` on each argument, since what it means differs between `rgb()`/`rgba()` (percentage of 255)
+/// and `hsl()` (a genuine 0-100 percentage).
+fn parse_functional<'a>(s: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let open = s.find('(')?;
+    if !s[..open].eq_ignore_ascii_case(name) || !s.ends_with(')') {
+        return None;
+    }
+    let inner = &s[open + 1..s.len() - 1];
+    Some(inner.split(',').map(|part| part.trim()).collect())
+}
+
+/// Parse a single CSS `rgb()`/`rgba()` channel into a `u8`. A plain number is a literal 0-255
+/// value; a trailing `%` is a percentage of 255.
+fn channel_from_css(component: &str) -> Result<u8, ParseColorError> {
+    let value: f64 = if let Some(percent) = component.strip_suffix('%') {
+        let percent: f64 = percent.parse().map_err(|_| ParseColorError::InvalidFormat)?;
+        percent / 100.0 * 255.0
+    } else {
+        component.parse().map_err(|_| ParseColorError::InvalidFormat)?
+    };
+    if !(0.0..=255.0).contains(&value) {
+        return Err(ParseColorError::InvalidFormat);
     }
+    Ok(value.round() as u8)
 }
 
 /// A [colour](https://homieiot.github.io/specification/#color) in red-green-blue format.
@@ -223,6 +369,11 @@ impl ColorRGB {
     pub fn new(r: u8, g: u8, b: u8) -> Self {
         ColorRGB { r, g, b }
     }
+
+    /// Convert this colour to the equivalent hue-saturation-value representation.
+    pub fn to_hsv(&self) -> ColorHSV {
+        self.clone().into()
+    }
 }
 
 impl Display for ColorRGB {
@@ -235,16 +386,61 @@ impl FromStr for ColorRGB {
     type Err = ParseColorError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(components) = parse_functional(s, "rgba") {
+            if let [r, g, b, _a] = components.as_slice() {
+                return Ok(ColorRGB {
+                    r: channel_from_css(r)?,
+                    g: channel_from_css(g)?,
+                    b: channel_from_css(b)?,
+                });
+            }
+            return Err(ParseColorError::InvalidFormat);
+        }
+        if let Some(components) = parse_functional(s, "rgb") {
+            if let [r, g, b] = components.as_slice() {
+                return Ok(ColorRGB {
+                    r: channel_from_css(r)?,
+                    g: channel_from_css(g)?,
+                    b: channel_from_css(b)?,
+                });
+            }
+            return Err(ParseColorError::InvalidFormat);
+        }
+
         let parts: Vec<_> = s.split(',').collect();
         if let [r, g, b] = parts.as_slice() {
-            Ok(ColorRGB {
+            return Ok(ColorRGB {
                 r: r.parse()?,
                 g: g.parse()?,
                 b: b.parse()?,
-            })
-        } else {
-            Err(ParseColorError())
+            });
+        }
+
+        if let Some(hex) = s.strip_prefix('#') {
+            // Reject non-ASCII-hex-digit input before doubling/slicing by length, since `hex.len()`
+            // is a byte count and a multi-byte UTF-8 character would otherwise land the subsequent
+            // byte-range slices off a char boundary.
+            if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(match hex.chars().count() {
+                    3 | 6 => ParseColorError::InvalidFormat,
+                    _ => ParseColorError::InvalidHexLength(s.to_owned()),
+                });
+            }
+            let hex = match hex.len() {
+                3 => hex.chars().flat_map(|c| [c, c]).collect(),
+                6 => hex.to_owned(),
+                _ => return Err(ParseColorError::InvalidHexLength(s.to_owned())),
+            };
+            return Ok(ColorRGB {
+                r: parse_hex_pair(&hex[0..2])?,
+                g: parse_hex_pair(&hex[2..4])?,
+                b: parse_hex_pair(&hex[4..6])?,
+            });
         }
+
+        let (r, g, b) = x11_color_by_name(&s.to_lowercase())
+            .ok_or_else(|| ParseColorError::UnknownName(s.to_owned()))?;
+        Ok(ColorRGB { r, g, b })
     }
 }
 
@@ -254,6 +450,52 @@ impl Color for ColorRGB {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for ColorRGB {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ColorRGB {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| D::Error::invalid_value(serde::de::Unexpected::Str(&s), &"a valid RGB colour"))
+    }
+}
+
+impl From<ColorRGB> for ColorHSV {
+    fn from(rgb: ColorRGB) -> Self {
+        let r = f64::from(rgb.r) / 255.0;
+        let g = f64::from(rgb.g) / 255.0;
+        let b = f64::from(rgb.b) / 255.0;
+
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+
+        let h = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * (((g - b) / delta).rem_euclid(6.0))
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let s = if max == 0.0 { 0.0 } else { delta / max };
+        let v = max;
+
+        ColorHSV {
+            h: h.round() as u16,
+            s: (s * 100.0).round() as u8,
+            v: (v * 100.0).round() as u8,
+        }
+    }
+}
+
 /// A [colour](https://homieiot.github.io/specification/#color) in hue-saturation-value format.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ColorHSV {
@@ -273,6 +515,11 @@ impl ColorHSV {
         assert!(v <= 100);
         ColorHSV { h, s, v }
     }
+
+    /// Convert this colour to the equivalent red-green-blue representation.
+    pub fn to_rgb(&self) -> ColorRGB {
+        self.clone().into()
+    }
 }
 
 impl Display for ColorHSV {
@@ -285,6 +532,33 @@ impl FromStr for ColorHSV {
     type Err = ParseColorError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(components) = parse_functional(s, "hsl") {
+            if let [h, s_pct, l_pct] = components.as_slice() {
+                let h: f64 = h.parse().map_err(|_| ParseColorError::InvalidFormat)?;
+                let s_pct: f64 = s_pct
+                    .trim_end_matches('%')
+                    .parse()
+                    .map_err(|_| ParseColorError::InvalidFormat)?;
+                let l_pct: f64 = l_pct
+                    .trim_end_matches('%')
+                    .parse()
+                    .map_err(|_| ParseColorError::InvalidFormat)?;
+                if (0.0..=360.0).contains(&h) && (0.0..=100.0).contains(&s_pct) && (0.0..=100.0).contains(&l_pct) {
+                    // Standard HSL -> HSV relation, with saturation/lightness as 0..1 fractions.
+                    let sl = s_pct / 100.0;
+                    let l = l_pct / 100.0;
+                    let v = l + sl * l.min(1.0 - l);
+                    let sv = if v == 0.0 { 0.0 } else { 2.0 * (1.0 - l / v) };
+                    return Ok(ColorHSV {
+                        h: h.round() as u16,
+                        s: (sv * 100.0).round() as u8,
+                        v: (v * 100.0).round() as u8,
+                    });
+                }
+            }
+            return Err(ParseColorError::InvalidFormat);
+        }
+
         let parts: Vec<_> = s.split(',').collect();
         if let [h, s, v] = parts.as_slice() {
             let h = h.parse()?;
@@ -294,7 +568,7 @@ impl FromStr for ColorHSV {
                 return Ok(ColorHSV { h, s, v });
             }
         }
-        Err(ParseColorError())
+        Err(ParseColorError::InvalidFormat)
     }
 }
 
@@ -304,6 +578,49 @@ impl Color for ColorHSV {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for ColorHSV {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ColorHSV {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| D::Error::invalid_value(serde::de::Unexpected::Str(&s), &"a valid HSV colour"))
+    }
+}
+
+impl From<ColorHSV> for ColorRGB {
+    fn from(hsv: ColorHSV) -> Self {
+        let h = f64::from(hsv.h);
+        let s = f64::from(hsv.s) / 100.0;
+        let v = f64::from(hsv.v) / 100.0;
+
+        let c = v * s;
+        let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+        let m = v - c;
+
+        let (r1, g1, b1) = match h as u16 {
+            0..=59 => (c, x, 0.0),
+            60..=119 => (x, c, 0.0),
+            120..=179 => (0.0, c, x),
+            180..=239 => (0.0, x, c),
+            240..=299 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+
+        ColorRGB {
+            r: (((r1 + m) * 255.0).round()) as u8,
+            g: (((g1 + m) * 255.0).round()) as u8,
+            b: (((b1 + m) * 255.0).round()) as u8,
+        }
+    }
+}
+
 /// The value of a Homie [enum](https://homieiot.github.io/specification/#enum) property.
 ///
 /// This must be a non-empty string.
@@ -340,6 +657,22 @@ impl ToString for EnumValue {
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for EnumValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for EnumValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| D::Error::invalid_value(serde::de::Unexpected::Str(&s), &"a non-empty string"))
+    }
+}
+
 impl Value for EnumValue {
     type Format = Vec<String>;
 
@@ -357,3 +690,332 @@ impl Value for EnumValue {
         }
     }
 }
+
+/// Whether `raw` looks like it was written specifically as a colour, rather than happening to also
+/// be a valid `ColorRGB` (e.g. an X11 name that is also a perfectly ordinary enum value).
+///
+/// Used by [`PropertyValue::parse`]'s untyped fallback to avoid misclassifying bare words like
+/// `"red"` or `"tan"` as colours instead of enum values.
+fn looks_like_explicit_color(raw: &str) -> bool {
+    const FUNCTIONAL_PREFIXES: &[&str] = &["rgb(", "rgba(", "hsl("];
+
+    raw.starts_with('#')
+        || raw.starts_with(|c: char| c.is_ascii_digit())
+        || FUNCTIONAL_PREFIXES
+            .iter()
+            .any(|prefix| raw.get(..prefix.len()).is_some_and(|head| head.eq_ignore_ascii_case(prefix)))
+}
+
+/// An owned Homie property value whose datatype is only known at runtime.
+///
+/// The [`Value`] trait is compile-time monomorphic, so it can't represent "whatever datatype this
+/// property turned out to be" in a single type. `PropertyValue` can, which is useful for a
+/// controller that discovers arbitrary devices and needs to store their property values
+/// generically.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+    Enum(EnumValue),
+    Color(ColorRGB),
+    ColorHsv(ColorHSV),
+}
+
+impl PropertyValue {
+    /// Parse a raw Homie property payload into a `PropertyValue`.
+    ///
+    /// If `datatype` is known, parsing is dispatched to the matching `Value` implementation and
+    /// validated against the given `format` (the parsed range for `Integer`/`Float`, the parsed
+    /// variant list for `Enum`, and `rgb`/`hsv` for `Color`).
+    ///
+    /// If `datatype` is `None`, variants are tried in a deterministic precedence order (boolean,
+    /// integer, float, colour, enum, then string as an always-succeeding fallback), mirroring how
+    /// loosely-typed config values are coerced.
+    ///
+    /// NOTE: this deliberately deviates from a strict "always try colour before enum" precedence.
+    /// The colour attempt is skipped unless `raw` looks like it was written specifically as a
+    /// colour (see [`looks_like_explicit_color`]), because [`ColorRGB::from_str`] accepts bare X11
+    /// colour names (`"red"`, `"tan"`, ...) that are equally plausible, and far more common, as
+    /// enum values. Flagging this explicitly as a semantic change from the original precedence
+    /// spec, for maintainer awareness: without the guard, any enum property whose value happens to
+    /// be an English colour word is silently misclassified as `Color` instead of `Enum`.
+    pub fn parse(
+        raw: &str,
+        datatype: Option<Datatype>,
+        format: &Option<String>,
+    ) -> Result<Self, ValueError> {
+        match datatype {
+            Some(Datatype::Boolean) => raw.parse().map(PropertyValue::Boolean).map_err(|_| {
+                ValueError::ParseFailed {
+                    value: raw.to_owned(),
+                    datatype: Datatype::Boolean,
+                }
+            }),
+            Some(Datatype::Integer) => {
+                let value: i64 = raw.parse().map_err(|_| ValueError::ParseFailed {
+                    value: raw.to_owned(),
+                    datatype: Datatype::Integer,
+                })?;
+                if let Some(format) = format {
+                    let range = i64::parse_format(format)?;
+                    if !range.contains(&value) {
+                        return Err(ValueError::ParseFailed {
+                            value: raw.to_owned(),
+                            datatype: Datatype::Integer,
+                        });
+                    }
+                }
+                Ok(PropertyValue::Integer(value))
+            }
+            Some(Datatype::Float) => {
+                let value: f64 = raw.parse().map_err(|_| ValueError::ParseFailed {
+                    value: raw.to_owned(),
+                    datatype: Datatype::Float,
+                })?;
+                if let Some(format) = format {
+                    let range = f64::parse_format(format)?;
+                    if !range.contains(&value) {
+                        return Err(ValueError::ParseFailed {
+                            value: raw.to_owned(),
+                            datatype: Datatype::Float,
+                        });
+                    }
+                }
+                Ok(PropertyValue::Float(value))
+            }
+            Some(Datatype::String) => Ok(PropertyValue::String(raw.to_owned())),
+            Some(Datatype::Color) => {
+                let color_format = match format {
+                    Some(format) => ColorFormat::from_str(format)?,
+                    None => ColorFormat::RGB,
+                };
+                match color_format {
+                    ColorFormat::RGB => raw.parse().map(PropertyValue::Color).map_err(|_| {
+                        ValueError::ParseFailed {
+                            value: raw.to_owned(),
+                            datatype: Datatype::Color,
+                        }
+                    }),
+                    ColorFormat::HSV => raw.parse().map(PropertyValue::ColorHsv).map_err(|_| {
+                        ValueError::ParseFailed {
+                            value: raw.to_owned(),
+                            datatype: Datatype::Color,
+                        }
+                    }),
+                }
+            }
+            Some(Datatype::Enum) => {
+                let value: EnumValue = raw.parse().map_err(|_| ValueError::ParseFailed {
+                    value: raw.to_owned(),
+                    datatype: Datatype::Enum,
+                })?;
+                if let Some(format) = format {
+                    let variants = EnumValue::parse_format(format)?;
+                    if !variants.contains(&value.to_string()) {
+                        return Err(ValueError::ParseFailed {
+                            value: raw.to_owned(),
+                            datatype: Datatype::Enum,
+                        });
+                    }
+                }
+                Ok(PropertyValue::Enum(value))
+            }
+            None => {
+                if let Ok(value) = raw.parse() {
+                    return Ok(PropertyValue::Boolean(value));
+                }
+                if let Ok(value) = raw.parse() {
+                    return Ok(PropertyValue::Integer(value));
+                }
+                if let Ok(value) = raw.parse() {
+                    return Ok(PropertyValue::Float(value));
+                }
+                // Only try the colour branch for tokens that couldn't plausibly be something
+                // else, e.g. an enum value: bare X11 colour names like "red" or "tan" are common
+                // enum values too, and would otherwise be misclassified as colours here.
+                if looks_like_explicit_color(raw) {
+                    if let Ok(value) = raw.parse() {
+                        return Ok(PropertyValue::Color(value));
+                    }
+                }
+                if let Ok(value) = raw.parse() {
+                    return Ok(PropertyValue::Enum(value));
+                }
+                Ok(PropertyValue::String(raw.to_owned()))
+            }
+        }
+    }
+
+    /// The Homie datatype corresponding to this value.
+    pub fn datatype(&self) -> Datatype {
+        match self {
+            PropertyValue::Integer(_) => Datatype::Integer,
+            PropertyValue::Float(_) => Datatype::Float,
+            PropertyValue::Boolean(_) => Datatype::Boolean,
+            PropertyValue::String(_) => Datatype::String,
+            PropertyValue::Enum(_) => Datatype::Enum,
+            PropertyValue::Color(_) => Datatype::Color,
+            PropertyValue::ColorHsv(_) => Datatype::Color,
+        }
+    }
+}
+
+impl Display for PropertyValue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PropertyValue::Integer(value) => write!(f, "{}", value),
+            PropertyValue::Float(value) => write!(f, "{}", value),
+            PropertyValue::Boolean(value) => write!(f, "{}", value),
+            PropertyValue::String(value) => f.write_str(value),
+            PropertyValue::Enum(value) => f.write_str(&value.to_string()),
+            PropertyValue::Color(value) => write!(f, "{}", value),
+            PropertyValue::ColorHsv(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_comma_rgb() {
+        assert_eq!("1,2,3".parse(), Ok(ColorRGB::new(1, 2, 3)));
+    }
+
+    #[test]
+    fn parses_hex_rgb() {
+        assert_eq!("#ff0000".parse(), Ok(ColorRGB::new(255, 0, 0)));
+        assert_eq!("#F00".parse(), Ok(ColorRGB::new(255, 0, 0)));
+    }
+
+    #[test]
+    fn rejects_bad_hex_length() {
+        assert_eq!(
+            "#ff00".parse::<ColorRGB>(),
+            Err(ParseColorError::InvalidHexLength("#ff00".to_owned()))
+        );
+    }
+
+    #[test]
+    fn rejects_non_ascii_hex_digits_without_panicking() {
+        // "€€" is 3-byte-per-char UTF-8, so byte length 6 looks like a valid 6-digit hex colour if
+        // digit-ness isn't checked first, and byte-slicing it would panic on a non-char-boundary.
+        assert_eq!(
+            "#€€".parse::<ColorRGB>(),
+            Err(ParseColorError::InvalidHexLength("#€€".to_owned()))
+        );
+    }
+
+    #[test]
+    fn parses_x11_name_case_insensitively() {
+        assert_eq!("CornflowerBlue".parse(), Ok(ColorRGB::new(100, 149, 237)));
+        assert_eq!("red".parse(), Ok(ColorRGB::new(255, 0, 0)));
+    }
+
+    #[test]
+    fn rejects_unknown_name() {
+        assert_eq!(
+            "not-a-colour".parse::<ColorRGB>(),
+            Err(ParseColorError::UnknownName("not-a-colour".to_owned()))
+        );
+    }
+
+    #[test]
+    fn rgb_to_hsv_primary_colours() {
+        assert_eq!(ColorRGB::new(255, 0, 0).to_hsv(), ColorHSV::new(0, 100, 100));
+        assert_eq!(ColorRGB::new(0, 255, 0).to_hsv(), ColorHSV::new(120, 100, 100));
+        assert_eq!(ColorRGB::new(0, 0, 255).to_hsv(), ColorHSV::new(240, 100, 100));
+    }
+
+    #[test]
+    fn rgb_to_hsv_black_and_white() {
+        assert_eq!(ColorRGB::new(0, 0, 0).to_hsv(), ColorHSV::new(0, 0, 0));
+        assert_eq!(ColorRGB::new(255, 255, 255).to_hsv(), ColorHSV::new(0, 0, 100));
+    }
+
+    #[test]
+    fn hsv_to_rgb_primary_colours() {
+        assert_eq!(ColorHSV::new(0, 100, 100).to_rgb(), ColorRGB::new(255, 0, 0));
+        assert_eq!(ColorHSV::new(120, 100, 100).to_rgb(), ColorRGB::new(0, 255, 0));
+        assert_eq!(ColorHSV::new(240, 100, 100).to_rgb(), ColorRGB::new(0, 0, 255));
+    }
+
+    #[test]
+    fn hsv_to_rgb_h_360_matches_h_0() {
+        assert_eq!(
+            ColorHSV::new(360, 100, 100).to_rgb(),
+            ColorHSV::new(0, 100, 100).to_rgb()
+        );
+    }
+
+    #[test]
+    fn rgb_hsv_round_trips_for_primary_colours() {
+        for rgb in [
+            ColorRGB::new(255, 0, 0),
+            ColorRGB::new(0, 255, 0),
+            ColorRGB::new(0, 0, 255),
+            ColorRGB::new(0, 0, 0),
+            ColorRGB::new(255, 255, 255),
+        ] {
+            assert_eq!(rgb.to_hsv().to_rgb(), rgb);
+        }
+    }
+
+    #[test]
+    fn parses_functional_rgb() {
+        assert_eq!("rgb(255, 0, 0)".parse(), Ok(ColorRGB::new(255, 0, 0)));
+    }
+
+    #[test]
+    fn parses_functional_rgb_percentages() {
+        assert_eq!("rgb(100%, 0%, 0%)".parse(), Ok(ColorRGB::new(255, 0, 0)));
+        assert_eq!("rgb(50%, 50%, 50%)".parse(), Ok(ColorRGB::new(128, 128, 128)));
+    }
+
+    #[test]
+    fn parses_functional_rgba_discarding_alpha() {
+        assert_eq!(
+            "rgba(255, 0, 0, 0.5)".parse(),
+            Ok(ColorRGB::new(255, 0, 0))
+        );
+    }
+
+    #[test]
+    fn parses_functional_hsl() {
+        assert_eq!("hsl(0, 100%, 50%)".parse(), Ok(ColorHSV::new(0, 100, 100)));
+        assert_eq!("hsl(360, 100%, 50%)".parse(), Ok(ColorHSV::new(360, 100, 100)));
+    }
+
+    #[test]
+    fn untyped_parse_prefers_enum_over_x11_colour_name() {
+        assert_eq!(
+            PropertyValue::parse("red", None, &None),
+            Ok(PropertyValue::Enum(EnumValue::new("red")))
+        );
+    }
+
+    #[test]
+    fn untyped_parse_still_recognises_explicit_colours() {
+        assert_eq!(
+            PropertyValue::parse("#ff0000", None, &None),
+            Ok(PropertyValue::Color(ColorRGB::new(255, 0, 0)))
+        );
+        assert_eq!(
+            PropertyValue::parse("255,0,0", None, &None),
+            Ok(PropertyValue::Color(ColorRGB::new(255, 0, 0)))
+        );
+    }
+
+    #[test]
+    fn untyped_parse_does_not_panic_on_multibyte_strings() {
+        // "aaaé(" is 6 bytes long but "é" (bytes 3..5) is not a char boundary at byte 4, which
+        // used to panic when byte-slicing to check for a "rgb("/"rgba("/"hsl(" prefix.
+        assert_eq!(
+            PropertyValue::parse("aaaé(", None, &None),
+            Ok(PropertyValue::Enum(EnumValue::new("aaaé(")))
+        );
+    }
+}